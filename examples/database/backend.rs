@@ -0,0 +1,85 @@
+/// Supported database backends
+///
+/// Selected at runtime from the `DATABASE_URL` scheme (see `from_url`); there
+/// is no cargo feature that narrows the choice. Abstracts the handful of
+/// places SQL text differs across MySQL, Postgres, and SQLite: placeholder
+/// syntax, the "current timestamp" function, and UUID column handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl Backend {
+    /// Detect the backend from a `DATABASE_URL`-style connection string scheme
+    pub fn from_url(url: &str) -> Result<Self, String> {
+        if url.starts_with("mysql://") {
+            Ok(Backend::MySql)
+        } else if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+            Ok(Backend::Postgres)
+        } else if url.starts_with("sqlite://") || url.starts_with("sqlite:") {
+            Ok(Backend::Sqlite)
+        } else {
+            Err(format!("unrecognized database URL scheme: {url}"))
+        }
+    }
+
+    /// Render the `index`-th bind parameter placeholder (1-based) for this backend
+    ///
+    /// MySQL and SQLite both use positional `?` placeholders; Postgres uses
+    /// numbered `$1..$n` placeholders.
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Backend::MySql | Backend::Sqlite => "?".to_string(),
+            Backend::Postgres => format!("${index}"),
+        }
+    }
+
+    /// SQL expression for the current timestamp
+    pub fn current_timestamp_fn(&self) -> &'static str {
+        match self {
+            Backend::MySql => "NOW()",
+            Backend::Postgres => "NOW()",
+            Backend::Sqlite => "CURRENT_TIMESTAMP",
+        }
+    }
+
+    /// Column type used for UUID primary/foreign keys
+    pub fn uuid_column_type(&self) -> &'static str {
+        match self {
+            Backend::MySql => "CHAR(36)",
+            Backend::Postgres => "UUID",
+            Backend::Sqlite => "TEXT",
+        }
+    }
+
+    /// Lowercase name used in health-check payloads and log lines
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Backend::MySql => "mysql",
+            Backend::Postgres => "postgres",
+            Backend::Sqlite => "sqlite",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_url_detects_each_backend() {
+        assert_eq!(Backend::from_url("mysql://root@localhost/db"), Ok(Backend::MySql));
+        assert_eq!(Backend::from_url("postgres://root@localhost/db"), Ok(Backend::Postgres));
+        assert_eq!(Backend::from_url("sqlite://./app.db"), Ok(Backend::Sqlite));
+        assert!(Backend::from_url("mongodb://localhost/db").is_err());
+    }
+
+    #[test]
+    fn test_placeholder_syntax_per_backend() {
+        assert_eq!(Backend::MySql.placeholder(1), "?");
+        assert_eq!(Backend::Sqlite.placeholder(2), "?");
+        assert_eq!(Backend::Postgres.placeholder(2), "$2");
+    }
+}
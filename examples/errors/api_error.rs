@@ -1,19 +1,57 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
+use std::time::Duration;
+
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
 
-/// Main API error type that encompasses all possible errors
-/// 
+/// A single field-level validation failure
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+/// Body of the `{ "error": { ... } }` envelope every `ApiError` serializes to
+///
+/// Kept as its own type (rather than building the `json!` object inline) so
+/// it can derive `ToSchema` and appear in generated OpenAPI docs as the
+/// documented shape of a failure response.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub numeric_code: u32,
+    pub message: String,
+    pub status: u16,
+    pub request_id: Option<String>,
+    pub fields: Option<Vec<FieldError>>,
+    pub retry_after_seconds: Option<u64>,
+}
+
+/// The full JSON body an `ApiError` renders as
+///
+/// Reference this in handler `#[utoipa::path]` annotations, e.g.
+/// `responses((status = 400, body = ErrorResponse), (status = 404, body = ErrorResponse))`,
+/// to give callers a typed, documented error contract instead of an ad-hoc
+/// JSON object.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
+}
+
+/// The underlying kind of API failure, independent of request context
+///
 /// This enum demonstrates proper error handling patterns:
 /// - Using thiserror for automatic trait implementations
 /// - Mapping different error sources to appropriate HTTP status codes
 /// - Providing helpful error messages for debugging
 #[derive(Debug, Error)]
-pub enum ApiError {
+pub enum ApiErrorKind {
     /// Database-related errors
     #[error("Database error: {0}")]
     Database(#[from] sqlx::Error),
@@ -22,6 +60,10 @@ pub enum ApiError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    /// Structured, per-field validation errors (one form, several bad inputs)
+    #[error("Validation error: {} field(s) failed validation", fields.len())]
+    ValidationErrors { fields: Vec<FieldError> },
+
     /// Authentication errors
     #[error("Authentication failed: {0}")]
     Authentication(String),
@@ -39,8 +81,12 @@ pub enum ApiError {
     Conflict(String),
 
     /// Rate limiting errors
-    #[error("Rate limit exceeded: {0}")]
-    RateLimit(String),
+    #[error("Rate limit exceeded: {message}")]
+    RateLimit {
+        message: String,
+        /// How long the caller should wait before retrying
+        retry_after: Option<Duration>,
+    },
 
     /// External service errors
     #[error("External service error: {0}")]
@@ -57,49 +103,133 @@ pub enum ApiError {
     /// Serialization/deserialization errors
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    /// Password hashing/verification failures
+    #[error("Password hashing error: {0}")]
+    PasswordHash(#[from] argon2::password_hash::Error),
+
+    /// A spawned task panicked or was cancelled before completing
+    #[error("Task join error: {0}")]
+    TaskJoin(#[from] tokio::task::JoinError),
+
+    /// Failure calling an external HTTP service
+    #[error("HTTP client error: {0}")]
+    HttpClient(#[from] reqwest::Error),
 }
 
-impl ApiError {
+impl From<validator::ValidationErrors> for ApiErrorKind {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let fields = errors
+            .field_errors()
+            .into_iter()
+            .flat_map(|(field, field_errors)| {
+                field_errors.iter().map(move |e| FieldError {
+                    field: field.to_string(),
+                    code: e.code.to_string(),
+                    message: e
+                        .message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| format!("{field} is invalid")),
+                })
+            })
+            .collect();
+
+        ApiErrorKind::ValidationErrors { fields }
+    }
+}
+
+impl ApiErrorKind {
     /// Get the appropriate HTTP status code for this error
     pub fn status_code(&self) -> StatusCode {
         match self {
-            ApiError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
-            ApiError::Authentication(_) => StatusCode::UNAUTHORIZED,
-            ApiError::Authorization(_) => StatusCode::FORBIDDEN,
-            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
-            ApiError::Conflict(_) => StatusCode::CONFLICT,
-            ApiError::RateLimit(_) => StatusCode::TOO_MANY_REQUESTS,
-            ApiError::ExternalService(_) => StatusCode::BAD_GATEWAY,
-            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            ApiError::Jwt(_) => StatusCode::UNAUTHORIZED,
-            ApiError::Serialization(_) => StatusCode::BAD_REQUEST,
+            ApiErrorKind::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiErrorKind::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiErrorKind::ValidationErrors { .. } => StatusCode::BAD_REQUEST,
+            ApiErrorKind::Authentication(_) => StatusCode::UNAUTHORIZED,
+            ApiErrorKind::Authorization(_) => StatusCode::FORBIDDEN,
+            ApiErrorKind::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiErrorKind::Conflict(_) => StatusCode::CONFLICT,
+            ApiErrorKind::RateLimit { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiErrorKind::ExternalService(_) => StatusCode::BAD_GATEWAY,
+            ApiErrorKind::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiErrorKind::Jwt(_) => StatusCode::UNAUTHORIZED,
+            ApiErrorKind::Serialization(_) => StatusCode::BAD_REQUEST,
+            ApiErrorKind::PasswordHash(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiErrorKind::TaskJoin(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiErrorKind::HttpClient(_) => StatusCode::BAD_GATEWAY,
         }
     }
 
     /// Get error code for client-side error handling
     pub fn error_code(&self) -> &'static str {
         match self {
-            ApiError::Database(_) => "DATABASE_ERROR",
-            ApiError::Validation(_) => "VALIDATION_ERROR",
-            ApiError::Authentication(_) => "AUTHENTICATION_ERROR",
-            ApiError::Authorization(_) => "AUTHORIZATION_ERROR",
-            ApiError::NotFound(_) => "NOT_FOUND",
-            ApiError::Conflict(_) => "CONFLICT",
-            ApiError::RateLimit(_) => "RATE_LIMIT_EXCEEDED",
-            ApiError::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
-            ApiError::Internal(_) => "INTERNAL_ERROR",
-            ApiError::Jwt(_) => "JWT_ERROR",
-            ApiError::Serialization(_) => "SERIALIZATION_ERROR",
+            ApiErrorKind::Database(_) => "DATABASE_ERROR",
+            ApiErrorKind::Validation(_) => "VALIDATION_ERROR",
+            ApiErrorKind::ValidationErrors { .. } => "VALIDATION_ERROR",
+            ApiErrorKind::Authentication(_) => "AUTHENTICATION_ERROR",
+            ApiErrorKind::Authorization(_) => "AUTHORIZATION_ERROR",
+            ApiErrorKind::NotFound(_) => "NOT_FOUND",
+            ApiErrorKind::Conflict(_) => "CONFLICT",
+            ApiErrorKind::RateLimit { .. } => "RATE_LIMIT_EXCEEDED",
+            ApiErrorKind::ExternalService(_) => "EXTERNAL_SERVICE_ERROR",
+            ApiErrorKind::Internal(_) => "INTERNAL_ERROR",
+            ApiErrorKind::Jwt(_) => "JWT_ERROR",
+            ApiErrorKind::Serialization(_) => "SERIALIZATION_ERROR",
+            ApiErrorKind::PasswordHash(_) => "INTERNAL_ERROR",
+            ApiErrorKind::TaskJoin(_) => "INTERNAL_ERROR",
+            ApiErrorKind::HttpClient(_) => "EXTERNAL_SERVICE_ERROR",
+        }
+    }
+
+    /// Get the stable numeric error code for client-side error handling
+    ///
+    /// Unlike `error_code()`'s string slug, this integer never changes even if
+    /// the slug is reworded, so clients can switch on it directly. Client
+    /// errors live in the `4xxxx` range, server errors in `5xxxx`:
+    ///
+    /// | Code    | Meaning                         |
+    /// |---------|---------------------------------|
+    /// | `40001` | Validation error                |
+    /// | `40003` | Authorization failed            |
+    /// | `40004` | Authentication failed           |
+    /// | `40005` | Invalid/expired JWT              |
+    /// | `40401` | Resource not found               |
+    /// | `40901` | Conflict                         |
+    /// | `42901` | Rate limit exceeded              |
+    /// | `40002` | Serialization error              |
+    /// | `50001` | Database error                  |
+    /// | `50201` | External service / HTTP client error |
+    /// | `50002` | Internal / password hashing / task join error |
+    pub fn numeric_code(&self) -> u32 {
+        match self {
+            ApiErrorKind::Validation(_) => 40001,
+            ApiErrorKind::ValidationErrors { .. } => 40001,
+            ApiErrorKind::Serialization(_) => 40002,
+            ApiErrorKind::Authorization(_) => 40003,
+            ApiErrorKind::Authentication(_) => 40004,
+            ApiErrorKind::Jwt(_) => 40005,
+            ApiErrorKind::NotFound(_) => 40401,
+            ApiErrorKind::Conflict(_) => 40901,
+            ApiErrorKind::RateLimit { .. } => 42901,
+            ApiErrorKind::Database(_) => 50001,
+            ApiErrorKind::Internal(_) => 50002,
+            ApiErrorKind::PasswordHash(_) => 50002,
+            ApiErrorKind::TaskJoin(_) => 50002,
+            ApiErrorKind::ExternalService(_) => 50201,
+            ApiErrorKind::HttpClient(_) => 50201,
         }
     }
 
     /// Check if this error should be logged (internal errors vs user errors)
     pub fn should_log(&self) -> bool {
         match self {
-            ApiError::Database(_) 
-            | ApiError::ExternalService(_) 
-            | ApiError::Internal(_) => true,
+            ApiErrorKind::Database(_)
+            | ApiErrorKind::ExternalService(_)
+            | ApiErrorKind::Internal(_)
+            | ApiErrorKind::PasswordHash(_)
+            | ApiErrorKind::TaskJoin(_)
+            | ApiErrorKind::HttpClient(_) => true,
             _ => false,
         }
     }
@@ -107,33 +237,143 @@ impl ApiError {
     /// Get user-friendly message (hiding internal details)
     pub fn user_message(&self) -> String {
         match self {
-            ApiError::Database(_) => "A database error occurred. Please try again later.".to_string(),
-            ApiError::ExternalService(_) => "An external service is temporarily unavailable.".to_string(),
-            ApiError::Internal(_) => "An internal error occurred. Please try again later.".to_string(),
+            ApiErrorKind::Database(_) => "A database error occurred. Please try again later.".to_string(),
+            ApiErrorKind::ExternalService(_) | ApiErrorKind::HttpClient(_) => {
+                "An external service is temporarily unavailable.".to_string()
+            }
+            ApiErrorKind::Internal(_) | ApiErrorKind::PasswordHash(_) | ApiErrorKind::TaskJoin(_) => {
+                "An internal error occurred. Please try again later.".to_string()
+            }
             // For other errors, use the display message
             _ => self.to_string(),
         }
     }
+
+    /// Per-field detail for `ValidationErrors`, if that's the kind of error this is
+    pub fn field_errors(&self) -> Option<&[FieldError]> {
+        match self {
+            ApiErrorKind::ValidationErrors { fields } => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// How long the caller should wait before retrying, if this is a `RateLimit` error
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ApiErrorKind::RateLimit { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// Main API error type that encompasses all possible errors
+///
+/// Wraps an `ApiErrorKind` together with the correlation ID of the request
+/// that produced it, so the ID can be echoed back to the client and included
+/// in the log line that support greps for.
+#[derive(Debug, Error)]
+#[error("{kind}")]
+pub struct ApiError {
+    pub kind: ApiErrorKind,
+    pub request_id: Option<String>,
+}
+
+/// Any source that converts to an `ApiErrorKind` also converts to a plain
+/// `ApiError` with no request ID attached, so `?` keeps working at call sites
+/// that haven't threaded a request ID through yet.
+impl<E> From<E> for ApiError
+where
+    ApiErrorKind: From<E>,
+{
+    fn from(err: E) -> Self {
+        ApiError {
+            kind: ApiErrorKind::from(err),
+            request_id: None,
+        }
+    }
+}
+
+impl ApiError {
+    /// Get the appropriate HTTP status code for this error
+    pub fn status_code(&self) -> StatusCode {
+        self.kind.status_code()
+    }
+
+    /// Get error code for client-side error handling
+    pub fn error_code(&self) -> &'static str {
+        self.kind.error_code()
+    }
+
+    /// Get the stable numeric error code for client-side error handling
+    pub fn numeric_code(&self) -> u32 {
+        self.kind.numeric_code()
+    }
+
+    /// Check if this error should be logged (internal errors vs user errors)
+    pub fn should_log(&self) -> bool {
+        self.kind.should_log()
+    }
+
+    /// Get user-friendly message (hiding internal details)
+    pub fn user_message(&self) -> String {
+        self.kind.user_message()
+    }
+
+    /// Per-field detail for `ValidationErrors`, if that's the kind of error this is
+    pub fn field_errors(&self) -> Option<&[FieldError]> {
+        self.kind.field_errors()
+    }
+
+    /// How long the caller should wait before retrying, if this is a `RateLimit` error
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.kind.retry_after()
+    }
+
+    /// Build the documented `ErrorResponse` body this error renders as
+    pub fn to_error_response(&self) -> ErrorResponse {
+        ErrorResponse {
+            error: ErrorBody {
+                code: self.error_code(),
+                numeric_code: self.numeric_code(),
+                message: self.user_message(),
+                status: self.status_code().as_u16(),
+                request_id: self.request_id.clone(),
+                fields: self.field_errors().map(|fields| fields.to_vec()),
+                retry_after_seconds: self.retry_after().map(|d| d.as_secs()),
+            }
+        }
+    }
+
+    /// Attach the correlation ID of the request that produced this error
+    ///
+    /// Pair with the `RequestId` extension stamped by `request_id_middleware`
+    /// from the `x-request-id` header so support can ask a user for the ID and grep logs directly.
+    pub fn with_request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request_id = Some(request_id.into());
+        self
+    }
 }
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status_code = self.status_code();
-        
+
         // Log internal errors
         if self.should_log() {
-            tracing::error!("API Error: {:?}", self);
+            tracing::error!(request_id = ?self.request_id, "API Error: {:?}", self.kind);
         }
 
-        let body = Json(json!({
-            "error": {
-                "code": self.error_code(),
-                "message": self.user_message(),
-                "status": status_code.as_u16()
+        let retry_after = self.retry_after();
+        let body = Json(self.to_error_response());
+
+        let mut response = (status_code, body).into_response();
+        if let Some(retry_after) = retry_after {
+            if let Ok(header_value) = HeaderValue::from_str(&retry_after.as_secs().to_string()) {
+                response.headers_mut().insert(header::RETRY_AFTER, header_value);
             }
-        }));
+        }
 
-        (status_code, body).into_response()
+        response
     }
 }
 
@@ -141,32 +381,46 @@ impl IntoResponse for ApiError {
 impl ApiError {
     /// Create a validation error with a custom message
     pub fn validation(message: impl Into<String>) -> Self {
-        Self::Validation(message.into())
+        ApiErrorKind::Validation(message.into()).into()
+    }
+
+    /// Create a structured, per-field validation error
+    pub fn validation_errors(fields: Vec<FieldError>) -> Self {
+        ApiErrorKind::ValidationErrors { fields }.into()
     }
 
     /// Create a not found error for a specific resource
     pub fn not_found(resource: impl Into<String>) -> Self {
-        Self::NotFound(format!("{} not found", resource.into()))
+        ApiErrorKind::NotFound(format!("{} not found", resource.into())).into()
     }
 
     /// Create an authentication error
     pub fn unauthorized(message: impl Into<String>) -> Self {
-        Self::Authentication(message.into())
+        ApiErrorKind::Authentication(message.into()).into()
     }
 
     /// Create an authorization error
     pub fn forbidden(message: impl Into<String>) -> Self {
-        Self::Authorization(message.into())
+        ApiErrorKind::Authorization(message.into()).into()
     }
 
     /// Create a conflict error
     pub fn conflict(message: impl Into<String>) -> Self {
-        Self::Conflict(message.into())
+        ApiErrorKind::Conflict(message.into()).into()
     }
 
     /// Create an internal error
     pub fn internal(message: impl Into<String>) -> Self {
-        Self::Internal(message.into())
+        ApiErrorKind::Internal(message.into()).into()
+    }
+
+    /// Create a rate limit error, optionally advising the caller when to retry
+    pub fn rate_limit(message: impl Into<String>, retry_after: Option<Duration>) -> Self {
+        ApiErrorKind::RateLimit {
+            message: message.into(),
+            retry_after,
+        }
+        .into()
     }
 }
 
@@ -192,6 +446,14 @@ mod tests {
         assert_eq!(ApiError::unauthorized("invalid").error_code(), "AUTHENTICATION_ERROR");
     }
 
+    #[test]
+    fn test_numeric_codes() {
+        assert_eq!(ApiError::validation("test").numeric_code(), 40001);
+        assert_eq!(ApiError::not_found("user").numeric_code(), 40401);
+        assert_eq!(ApiError::unauthorized("invalid").numeric_code(), 40004);
+        assert_eq!(ApiError::internal("test").numeric_code(), 50002);
+    }
+
     #[test]
     fn test_should_log() {
         assert!(ApiError::internal("test").should_log());
@@ -202,11 +464,97 @@ mod tests {
     #[test]
     fn test_user_messages() {
         // Internal errors should have generic messages
-        let db_error = ApiError::Database(sqlx::Error::RowNotFound);
+        let db_error: ApiError = ApiErrorKind::Database(sqlx::Error::RowNotFound).into();
         assert_eq!(db_error.user_message(), "A database error occurred. Please try again later.");
 
         // User errors should show actual message
         let validation_error = ApiError::validation("Invalid email format");
         assert_eq!(validation_error.user_message(), "Validation error: Invalid email format");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_with_request_id_sets_request_id() {
+        let error = ApiError::not_found("user").with_request_id("req-123");
+        assert_eq!(error.request_id, Some("req-123".to_string()));
+    }
+
+    #[test]
+    fn test_validation_errors_carries_field_detail() {
+        let error = ApiError::validation_errors(vec![FieldError {
+            field: "email".to_string(),
+            code: "format".to_string(),
+            message: "email is not a valid address".to_string(),
+        }]);
+
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(error.numeric_code(), 40001);
+        let fields = error.field_errors().expect("validation errors carry fields");
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field, "email");
+    }
+
+    #[test]
+    fn test_password_hash_error_is_internal_and_logged() {
+        let error: ApiError = argon2::password_hash::Error::Password.into();
+
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.numeric_code(), 50002);
+        assert!(error.should_log());
+        assert_eq!(error.user_message(), "An internal error occurred. Please try again later.");
+    }
+
+    #[tokio::test]
+    async fn test_task_join_error_is_internal_and_logged() {
+        let handle = tokio::spawn(async { panic!("boom") });
+        let join_err = handle.await.expect_err("panicking task should produce a join error");
+
+        let error: ApiError = join_err.into();
+        assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(error.numeric_code(), 50002);
+        assert!(error.should_log());
+    }
+
+    #[test]
+    fn test_to_error_response_mirrors_accessors() {
+        let error = ApiError::not_found("user").with_request_id("req-123");
+        let response = error.to_error_response();
+
+        assert_eq!(response.error.code, error.error_code());
+        assert_eq!(response.error.numeric_code, error.numeric_code());
+        assert_eq!(response.error.status, error.status_code().as_u16());
+        assert_eq!(response.error.request_id, Some("req-123".to_string()));
+    }
+
+    #[test]
+    fn test_rate_limit_carries_retry_after() {
+        let error = ApiError::rate_limit("too many requests", Some(Duration::from_secs(30)));
+
+        assert_eq!(error.status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.numeric_code(), 42901);
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_rate_limit_without_retry_after() {
+        let error = ApiError::rate_limit("too many requests", None);
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_from_validator_validation_errors() {
+        use validator::Validate;
+
+        #[derive(Validate)]
+        struct SignupForm {
+            #[validate(email)]
+            email: String,
+        }
+
+        let form = SignupForm { email: "not-an-email".to_string() };
+        let validation_errors = form.validate().expect_err("invalid email should fail validation");
+
+        let error: ApiError = validation_errors.into();
+        let fields = error.field_errors().expect("validation errors carry fields");
+        assert_eq!(fields[0].field, "email");
+    }
+}
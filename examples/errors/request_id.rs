@@ -0,0 +1,47 @@
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use uuid::Uuid;
+
+/// Header clients may send to propagate a correlation ID, or that the server
+/// assigns when they don't
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Request extension carrying the correlation ID assigned to a single request
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Stamp every request with a correlation ID
+///
+/// Reuses an inbound `x-request-id` header when present, otherwise generates a
+/// new UUID. Stores it as a request extension so handlers can pull it out and
+/// pass it to `ApiError::with_request_id`, and echoes it back on the response
+/// headers so the caller can report it to support.
+pub async fn request_id_middleware(mut req: Request, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let mut response = next.run(req).await;
+    if let Ok(header_value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, header_value);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_id_header_name() {
+        assert_eq!(REQUEST_ID_HEADER, "x-request-id");
+    }
+}
@@ -1,5 +1,5 @@
 use axum::{
-    extract::{Path, Query, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::Json,
 };
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 use crate::{
     errors::ApiError,
+    errors::request_id::RequestId,
     models::Task,
     services::TaskService,
     AppState,
@@ -78,15 +79,17 @@ pub struct TaskListResponse {
 /// JSON response containing paginated task list or error
 pub async fn list_tasks(
     State(state): State<AppState>,
+    Extension(RequestId(request_id)): Extension<RequestId>,
     Query(query): Query<ListTasksQuery>,
 ) -> Result<Json<TaskListResponse>, ApiError> {
     let page = query.page.unwrap_or(1);
     let limit = query.limit.unwrap_or(20).min(100); // Cap at 100 items per page
-    
+
     let task_service = TaskService::new(&state.db);
     let (tasks, total) = task_service
         .list_with_filters(page, limit, query.status, query.priority)
-        .await?;
+        .await
+        .map_err(|err| ApiError::from(err).with_request_id(request_id))?;
 
     let response = TaskListResponse {
         tasks,
@@ -110,10 +113,14 @@ pub async fn list_tasks(
 /// JSON response containing task data or 404 error
 pub async fn get_task(
     State(state): State<AppState>,
+    Extension(RequestId(request_id)): Extension<RequestId>,
     Path(task_id): Path<Uuid>,
 ) -> Result<Json<Task>, ApiError> {
     let task_service = TaskService::new(&state.db);
-    let task = task_service.get_by_id(task_id).await?;
+    let task = task_service
+        .get_by_id(task_id)
+        .await
+        .map_err(|err| ApiError::from(err).with_request_id(request_id))?;
 
     Ok(Json(task))
 }
@@ -130,19 +137,23 @@ pub async fn get_task(
 /// JSON response containing created task with 201 status or error
 pub async fn create_task(
     State(state): State<AppState>,
+    Extension(RequestId(request_id)): Extension<RequestId>,
     Json(payload): Json<CreateTaskRequest>,
 ) -> Result<(StatusCode, Json<Task>), ApiError> {
     // Validate input
     if payload.title.trim().is_empty() {
-        return Err(ApiError::Validation("Title cannot be empty".to_string()));
+        return Err(ApiError::validation("Title cannot be empty").with_request_id(request_id));
     }
 
     if payload.title.len() > 255 {
-        return Err(ApiError::Validation("Title must be 255 characters or less".to_string()));
+        return Err(ApiError::validation("Title must be 255 characters or less").with_request_id(request_id));
     }
 
     let task_service = TaskService::new(&state.db);
-    let task = task_service.create(payload).await?;
+    let task = task_service
+        .create(payload)
+        .await
+        .map_err(|err| ApiError::from(err).with_request_id(request_id))?;
 
     Ok((StatusCode::CREATED, Json(task)))
 }
@@ -160,21 +171,25 @@ pub async fn create_task(
 /// JSON response containing updated task or error
 pub async fn update_task(
     State(state): State<AppState>,
+    Extension(RequestId(request_id)): Extension<RequestId>,
     Path(task_id): Path<Uuid>,
     Json(payload): Json<UpdateTaskRequest>,
 ) -> Result<Json<Task>, ApiError> {
     // Validate input if title is being updated
     if let Some(ref title) = payload.title {
         if title.trim().is_empty() {
-            return Err(ApiError::Validation("Title cannot be empty".to_string()));
+            return Err(ApiError::validation("Title cannot be empty").with_request_id(request_id));
         }
         if title.len() > 255 {
-            return Err(ApiError::Validation("Title must be 255 characters or less".to_string()));
+            return Err(ApiError::validation("Title must be 255 characters or less").with_request_id(request_id));
         }
     }
 
     let task_service = TaskService::new(&state.db);
-    let task = task_service.update(task_id, payload).await?;
+    let task = task_service
+        .update(task_id, payload)
+        .await
+        .map_err(|err| ApiError::from(err).with_request_id(request_id))?;
 
     Ok(Json(task))
 }
@@ -191,10 +206,14 @@ pub async fn update_task(
 /// 204 No Content status or error
 pub async fn delete_task(
     State(state): State<AppState>,
+    Extension(RequestId(request_id)): Extension<RequestId>,
     Path(task_id): Path<Uuid>,
 ) -> Result<StatusCode, ApiError> {
     let task_service = TaskService::new(&state.db);
-    task_service.delete(task_id).await?;
+    task_service
+        .delete(task_id)
+        .await
+        .map_err(|err| ApiError::from(err).with_request_id(request_id))?;
 
     Ok(StatusCode::NO_CONTENT)
 }
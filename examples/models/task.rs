@@ -1,8 +1,15 @@
+use std::str::FromStr;
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use sqlx::{FromRow, MySql, Type};
 use uuid::Uuid;
 
+use crate::database::backend::Backend;
+
 /// Task priority levels for categorization
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 #[sqlx(type_name = "task_priority", rename_all = "lowercase")]
@@ -27,6 +34,8 @@ pub enum TaskStatus {
     InProgress,
     Completed,
     Cancelled,
+    /// Exhausted its retry budget; `error_message` holds the last failure
+    Failed,
 }
 
 impl Default for TaskStatus {
@@ -50,6 +59,26 @@ pub struct Task {
     pub priority: TaskPriority,
     pub status: TaskStatus,
     pub user_id: Uuid,
+    /// Identifies which registered worker handler should execute this task
+    pub task_type: String,
+    /// Number of times this task has been attempted and failed
+    pub retries: i32,
+    /// Maximum number of attempts before the task is moved to `Failed`
+    pub max_retries: i32,
+    /// Error from the most recent failed attempt, set by `record_failure`
+    pub error_message: Option<String>,
+    /// Earliest time a worker may claim this task; used to implement backoff
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Cron expression; when set, completing this task re-enqueues it.
+    ///
+    /// Parsed with the `cron` crate, which requires a leading seconds field
+    /// (6 or 7 space-separated fields: `sec min hour dom month dow [year]`) —
+    /// the standard 5-field Unix form (`min hour dom month dow`) is rejected.
+    pub cron_schedule: Option<String>,
+    /// SHA-256 digest of the dedup-relevant fields, set when the task was created as unique
+    pub uniq_hash: Option<String>,
+    /// Arbitrary job arguments, stored as MySQL JSON and interpreted per `task_type`
+    pub metadata: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub due_date: Option<DateTime<Utc>>,
@@ -59,6 +88,11 @@ pub struct Task {
 impl Task {
     /// Create a new task with default values
     pub fn new(title: String, user_id: Uuid) -> Self {
+        Self::new_with_type(title, user_id, "default".to_string())
+    }
+
+    /// Create a new task of a specific `task_type`, for dispatch to a worker handler
+    pub fn new_with_type(title: String, user_id: Uuid, task_type: String) -> Self {
         let now = Utc::now();
         Self {
             id: Uuid::new_v4(),
@@ -67,6 +101,14 @@ impl Task {
             priority: TaskPriority::default(),
             status: TaskStatus::default(),
             user_id,
+            task_type,
+            retries: 0,
+            max_retries: 3,
+            error_message: None,
+            scheduled_at: None,
+            cron_schedule: None,
+            uniq_hash: None,
+            metadata: serde_json::Value::Null,
             created_at: now,
             updated_at: now,
             due_date: None,
@@ -91,6 +133,25 @@ impl Task {
         }
     }
 
+    /// Record a failed execution attempt, retrying with exponential backoff or
+    /// giving up and transitioning to `Failed` once `max_retries` is exhausted
+    pub fn record_failure(&mut self, err: &str, base_backoff: Duration) {
+        self.retries += 1;
+        self.error_message = Some(err.to_string());
+
+        if self.retries < self.max_retries {
+            let backoff_secs = base_backoff.as_secs().saturating_mul(1 << self.retries.min(16));
+            let backoff_secs = backoff_secs.min(3600);
+            self.status = TaskStatus::Pending;
+            self.scheduled_at = Some(Utc::now() + chrono::Duration::seconds(backoff_secs as i64));
+        } else {
+            self.status = TaskStatus::Failed;
+            self.scheduled_at = None;
+        }
+
+        self.updated_at = Utc::now();
+    }
+
     /// Check if task is overdue
     pub fn is_overdue(&self) -> bool {
         if let Some(due_date) = self.due_date {
@@ -101,6 +162,43 @@ impl Task {
         false
     }
 
+    /// If this task has a `cron_schedule`, build the next occurrence as a fresh
+    /// `Pending` task scheduled for the expression's next upcoming fire time
+    pub fn next_occurrence(&self) -> Option<Task> {
+        let expr = self.cron_schedule.as_ref()?;
+        let schedule = cron::Schedule::from_str(expr).ok()?;
+        let next_fire = schedule.upcoming(Utc).next()?;
+
+        let mut next = Task::new_with_type(self.title.clone(), self.user_id, self.task_type.clone());
+        next.description = self.description.clone();
+        next.priority = self.priority.clone();
+        next.cron_schedule = self.cron_schedule.clone();
+        next.metadata = self.metadata.clone();
+        next.scheduled_at = Some(next_fire);
+        Some(next)
+    }
+
+    /// Hash the dedup-relevant fields so identical logical tasks collide on insert
+    ///
+    /// Normalizes `description` (trimmed) alongside `task_type` and `title` so that
+    /// whitespace-only differences don't defeat deduplication.
+    pub fn compute_uniq_hash(&self) -> String {
+        let normalized_description = self.description.as_deref().unwrap_or("").trim();
+        let canonical = format!("{}\u{0}{}\u{0}{}", self.task_type, self.title, normalized_description);
+
+        let mut hasher = Sha256::new();
+        hasher.update(canonical.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Deserialize `metadata` into a strongly-typed job argument struct
+    ///
+    /// Handlers registered for a given `task_type` use this to recover the
+    /// arguments they were enqueued with.
+    pub fn payload_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.metadata.clone())
+    }
+
     /// Get task age in days
     pub fn age_in_days(&self) -> i64 {
         let now = Utc::now();
@@ -115,15 +213,43 @@ pub struct CreateTaskRequest {
     pub description: Option<String>,
     pub priority: Option<TaskPriority>,
     pub due_date: Option<DateTime<Utc>>,
+    /// Worker handler this task should be dispatched to; defaults to `"default"`
+    pub task_type: Option<String>,
+    /// Cron expression for a recurring task. Requires a leading seconds field
+    /// (e.g. `"0 0 0 * * * *"` for daily at midnight) — the standard 5-field
+    /// Unix form is not accepted.
+    pub cron_schedule: Option<String>,
+    /// When `true`, the created task is deduplicated via `Task::compute_uniq_hash`
+    pub unique: Option<bool>,
+    /// Arbitrary job arguments for the registered `task_type` handler
+    pub metadata: Option<serde_json::Value>,
 }
 
 impl CreateTaskRequest {
+    /// Set `task_type` and `metadata` together from a strongly-typed job argument struct
+    ///
+    /// Lets callers register a job kind and its arguments in one call instead of
+    /// hand-rolling the `serde_json::to_value` conversion.
+    pub fn with_payload<T: Serialize>(mut self, task_type: impl Into<String>, payload: &T) -> Result<Self, serde_json::Error> {
+        self.task_type = Some(task_type.into());
+        self.metadata = Some(serde_json::to_value(payload)?);
+        Ok(self)
+    }
+
     /// Convert to Task model
     pub fn into_task(self, user_id: Uuid) -> Task {
-        let mut task = Task::new(self.title, user_id);
+        let task_type = self.task_type.unwrap_or_else(|| "default".to_string());
+        let mut task = Task::new_with_type(self.title, user_id, task_type);
         task.description = self.description;
         task.priority = self.priority.unwrap_or_default();
         task.due_date = self.due_date;
+        task.cron_schedule = self.cron_schedule;
+        task.metadata = self.metadata.unwrap_or(serde_json::Value::Null);
+
+        if self.unique.unwrap_or(false) {
+            task.uniq_hash = Some(task.compute_uniq_hash());
+        }
+
         task
     }
 
@@ -132,6 +258,17 @@ impl CreateTaskRequest {
         if self.title.trim().is_empty() {
             return Err("Title cannot be empty".to_string());
         }
+
+        if let Some(cron_schedule) = &self.cron_schedule {
+            if cron::Schedule::from_str(cron_schedule).is_err() {
+                return Err(
+                    "cron_schedule is not a valid cron expression; it requires a leading \
+                     seconds field (6 or 7 fields: sec min hour dom month dow [year]), not \
+                     the standard 5-field Unix form"
+                        .to_string(),
+                );
+            }
+        }
         
         if self.title.len() > 255 {
             return Err("Title must be 255 characters or less".to_string());
@@ -161,6 +298,7 @@ pub struct UpdateTaskRequest {
     pub priority: Option<TaskPriority>,
     pub status: Option<TaskStatus>,
     pub due_date: Option<DateTime<Utc>>,
+    pub metadata: Option<serde_json::Value>,
 }
 
 impl UpdateTaskRequest {
@@ -169,23 +307,27 @@ impl UpdateTaskRequest {
         if let Some(title) = &self.title {
             task.title = title.clone();
         }
-        
+
         if let Some(description) = &self.description {
             task.description = Some(description.clone());
         }
-        
+
         if let Some(priority) = &self.priority {
             task.priority = priority.clone();
         }
-        
+
         if let Some(status) = &self.status {
             task.update_status(status.clone());
         }
-        
+
         if let Some(due_date) = self.due_date {
             task.due_date = Some(due_date);
         }
-        
+
+        if let Some(metadata) = &self.metadata {
+            task.metadata = metadata.clone();
+        }
+
         task.updated_at = Utc::now();
     }
 
@@ -228,36 +370,40 @@ pub struct TaskQuery {
 }
 
 impl TaskQuery {
-    /// Build SQL WHERE clause based on filters
-    pub fn build_where_clause(&self) -> (String, Vec<String>) {
+    /// Build a SQL WHERE clause based on filters, using the placeholder syntax
+    /// and timestamp function appropriate for `backend`
+    pub fn build_where_clause(&self, backend: Backend) -> (String, Vec<String>) {
         let mut conditions = Vec::new();
         let mut params = Vec::new();
-        
+
         if let Some(status) = &self.status {
-            conditions.push("status = ?".to_string());
+            conditions.push(format!("status = {}", backend.placeholder(params.len() + 1)));
             params.push(format!("{:?}", status).to_lowercase());
         }
-        
+
         if let Some(priority) = &self.priority {
-            conditions.push("priority = ?".to_string());
+            conditions.push(format!("priority = {}", backend.placeholder(params.len() + 1)));
             params.push(format!("{:?}", priority).to_lowercase());
         }
-        
+
         if let Some(user_id) = &self.user_id {
-            conditions.push("user_id = ?".to_string());
+            conditions.push(format!("user_id = {}", backend.placeholder(params.len() + 1)));
             params.push(user_id.to_string());
         }
-        
+
         if let Some(true) = self.overdue {
-            conditions.push("due_date < NOW() AND status != 'completed'".to_string());
+            conditions.push(format!(
+                "due_date < {} AND status != 'completed'",
+                backend.current_timestamp_fn()
+            ));
         }
-        
+
         let where_clause = if conditions.is_empty() {
             "1=1".to_string()
         } else {
             conditions.join(" AND ")
         };
-        
+
         (where_clause, params)
     }
     
@@ -290,6 +436,13 @@ mod tests {
         assert!(task.completed_at.is_none());
     }
 
+    #[test]
+    fn test_in_progress_lowercase_encoding_has_no_underscore() {
+        // Guards against hand-typed SQL literals drifting from the `rename_all =
+        // "lowercase"` encoding sqlx actually produces (`"inprogress"`, not `"in_progress"`).
+        assert_eq!(format!("{:?}", TaskStatus::InProgress).to_lowercase(), "inprogress");
+    }
+
     #[test]
     fn test_task_completion() {
         let user_id = Uuid::new_v4();
@@ -301,6 +454,82 @@ mod tests {
         assert!(task.completed_at.is_some());
     }
 
+    #[test]
+    fn test_record_failure_retries_with_backoff() {
+        let user_id = Uuid::new_v4();
+        let mut task = Task::new("Test Task".to_string(), user_id);
+
+        task.record_failure("boom", Duration::from_secs(1));
+
+        assert_eq!(task.retries, 1);
+        assert_eq!(task.status, TaskStatus::Pending);
+        assert_eq!(task.error_message, Some("boom".to_string()));
+        assert!(task.scheduled_at.is_some());
+    }
+
+    #[test]
+    fn test_record_failure_exhausts_retries() {
+        let user_id = Uuid::new_v4();
+        let mut task = Task::new("Test Task".to_string(), user_id);
+        task.max_retries = 1;
+
+        task.record_failure("boom", Duration::from_secs(1));
+
+        assert_eq!(task.status, TaskStatus::Failed);
+        assert!(task.scheduled_at.is_none());
+    }
+
+    #[test]
+    fn test_compute_uniq_hash_is_stable_and_ignores_whitespace() {
+        let user_id = Uuid::new_v4();
+        let mut a = Task::new_with_type("Send welcome email".to_string(), user_id, "email".to_string());
+        a.description = Some("  payload  ".to_string());
+
+        let mut b = Task::new_with_type("Send welcome email".to_string(), user_id, "email".to_string());
+        b.description = Some("payload".to_string());
+
+        assert_eq!(a.compute_uniq_hash(), b.compute_uniq_hash());
+    }
+
+    #[test]
+    fn test_payload_as_roundtrips_typed_job_args() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct SendEmailArgs {
+            to: String,
+        }
+
+        let user_id = Uuid::new_v4();
+        let mut task = Task::new_with_type("Send welcome email".to_string(), user_id, "email".to_string());
+        task.metadata = serde_json::json!({ "to": "user@example.com" });
+
+        let args: SendEmailArgs = task.payload_as().expect("metadata matches SendEmailArgs");
+        assert_eq!(args, SendEmailArgs { to: "user@example.com".to_string() });
+    }
+
+    #[test]
+    fn test_with_payload_sets_task_type_and_metadata() {
+        #[derive(Serialize)]
+        struct SendEmailArgs {
+            to: String,
+        }
+
+        let request = CreateTaskRequest {
+            title: "Send welcome email".to_string(),
+            description: None,
+            priority: None,
+            due_date: None,
+            task_type: None,
+            cron_schedule: None,
+            unique: None,
+            metadata: None,
+        }
+        .with_payload("email", &SendEmailArgs { to: "user@example.com".to_string() })
+        .expect("payload serializes");
+
+        assert_eq!(request.task_type, Some("email".to_string()));
+        assert_eq!(request.metadata, Some(serde_json::json!({ "to": "user@example.com" })));
+    }
+
     #[test]
     fn test_overdue_task() {
         let user_id = Uuid::new_v4();
@@ -317,6 +546,10 @@ mod tests {
             description: None,
             priority: None,
             due_date: None,
+            task_type: None,
+            cron_schedule: None,
+            unique: None,
+            metadata: None,
         };
         
         assert!(request.validate().is_err());
@@ -329,11 +562,74 @@ mod tests {
             description: None,
             priority: None,
             due_date: Some(Utc::now() - chrono::Duration::days(1)),
+            task_type: None,
+            cron_schedule: None,
+            unique: None,
+            metadata: None,
         };
         
         assert!(request.validate().is_err());
     }
 
+    #[test]
+    fn test_invalid_cron_schedule_validation() {
+        let request = CreateTaskRequest {
+            title: "Valid Title".to_string(),
+            description: None,
+            priority: None,
+            due_date: None,
+            task_type: None,
+            cron_schedule: Some("not a cron expression".to_string()),
+            unique: None,
+            metadata: None,
+        };
+
+        assert!(request.validate().is_err());
+    }
+
+    #[test]
+    fn test_five_field_unix_cron_schedule_is_rejected() {
+        let request = CreateTaskRequest {
+            title: "Valid Title".to_string(),
+            description: None,
+            priority: None,
+            due_date: None,
+            task_type: None,
+            cron_schedule: Some("0 9 * * 1".to_string()),
+            unique: None,
+            metadata: None,
+        };
+
+        let err = request.validate().expect_err("5-field Unix cron form is not accepted");
+        assert!(err.contains("seconds field"));
+    }
+
+    #[test]
+    fn test_next_occurrence_preserves_fields() {
+        let user_id = Uuid::new_v4();
+        let mut task = Task::new_with_type("Nightly Job".to_string(), user_id, "report".to_string());
+        task.cron_schedule = Some("0 0 0 * * * *".to_string());
+
+        let next = task.next_occurrence().expect("valid cron schedule");
+
+        assert_eq!(next.title, "Nightly Job");
+        assert_eq!(next.task_type, "report");
+        assert_eq!(next.status, TaskStatus::Pending);
+        assert!(next.scheduled_at.is_some());
+    }
+
+    #[test]
+    fn test_next_occurrence_carries_over_metadata() {
+        let user_id = Uuid::new_v4();
+        let mut task = Task::new_with_type("Nightly Job".to_string(), user_id, "report".to_string());
+        task.cron_schedule = Some("0 0 0 * * * *".to_string());
+        task.metadata = serde_json::json!({ "report_kind": "daily_summary" });
+
+        let next = task.next_occurrence().expect("valid cron schedule");
+
+        assert_eq!(next.metadata, serde_json::json!({ "report_kind": "daily_summary" }));
+    }
+
     #[test]
     fn test_query_where_clause() {
         let query = TaskQuery {
@@ -345,12 +641,29 @@ mod tests {
             limit: None,
         };
         
-        let (where_clause, params) = query.build_where_clause();
+        let (where_clause, params) = query.build_where_clause(Backend::MySql);
         assert!(where_clause.contains("status = ?"));
         assert!(where_clause.contains("priority = ?"));
         assert_eq!(params.len(), 2);
     }
 
+    #[test]
+    fn test_query_where_clause_postgres_placeholders() {
+        let query = TaskQuery {
+            status: Some(TaskStatus::Pending),
+            priority: Some(TaskPriority::High),
+            user_id: None,
+            overdue: None,
+            page: None,
+            limit: None,
+        };
+
+        let (where_clause, params) = query.build_where_clause(Backend::Postgres);
+        assert!(where_clause.contains("status = $1"));
+        assert!(where_clause.contains("priority = $2"));
+        assert_eq!(params.len(), 2);
+    }
+
     #[test]
     fn test_pagination() {
         let query = TaskQuery {